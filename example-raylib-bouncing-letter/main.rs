@@ -7,7 +7,7 @@ fn main() {
         .size(window_width, window_height)
         .title("lily ♥ raylib")
         .build();
-    let mut lily_state: lily::State = lily::initial_state(lily::Window_height·window_width {
+    let mut lily_state: lily::State = lily::initial_state(lily::Record_window_height_window_width {
         window_width: window_width as lily::Dec,
         window_height: window_height as lily::Dec,
     });
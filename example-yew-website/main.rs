@@ -295,7 +295,7 @@ fn web_sys_js_value_to_lily_json(
                         web_sys::js_sys::Reflect::get(js_object, &key).ok();
                     maybe_key
                         .zip(maybe_value)
-                        .map(|(key, value)| lily::Key·value {
+                        .map(|(key, value)| lily::Record_key_value {
                             key: lily::Str::from_string(key),
                             value: web_sys_js_value_to_lily_json(&value),
                         })
@@ -0,0 +1,237 @@
+//! The lily compiler as a library.
+//! Mainly useful to regenerate the rust module from a cargo build script
+//! so it is up to date on every cargo build, without running lily build or the language server:
+//! ```ignore
+//! // build.rs, with lily under [build-dependencies]
+//! fn main() -> Result<(), String> {
+//!     lily::build_script_generate_rust_module(std::path::Path::new("lily.lily"))
+//! }
+//! ```
+
+// lsp lily reports this specific error even when it is allowed in the Cargo.toml
+#![allow(non_upper_case_globals)]
+
+// the compiler, command line interface and language server
+mod compiler;
+// just to get analysis on lily_core, not actually used here
+mod lily_core;
+
+pub use compiler::{
+    LilyCoreInclusion, LilyRustSerialization, LilyRustSourceMap, LilyRustStandardLibrary,
+    LilyRustThreading, command_line_main,
+};
+
+/// How compile_lily_source_to_rust generates rust, see lily build
+#[derive(Clone)]
+pub struct LilyRustOptions {
+    pub core_inclusion: LilyCoreInclusion,
+    pub threading: LilyRustThreading,
+    pub serialization: LilyRustSerialization,
+    pub source_map: LilyRustSourceMap,
+    pub standard_library: LilyRustStandardLibrary,
+    /// names of the declarations the host code uses.
+    /// When empty, all declarations are generated
+    pub entries: Vec<String>,
+}
+/// The options lily build and the language server use for the given .lily file:
+/// the defaults, adjusted by `[package.metadata.lily]` in the nearest Cargo.toml
+///
+/// # Errors
+/// When the entries in Cargo.toml are not an array of strings
+pub fn lily_rust_options_for_input_file_path(
+    input_file_path: &std::path::Path,
+) -> Result<LilyRustOptions, String> {
+    let cargo_toml_options: compiler::LilyCargoTomlOptions =
+        compiler::lily_cargo_toml_options_for_input_file_path(input_file_path);
+    Ok(LilyRustOptions {
+        core_inclusion: cargo_toml_options.core_inclusion(),
+        threading: cargo_toml_options.rust_threading,
+        serialization: cargo_toml_options.rust_serialization,
+        source_map: cargo_toml_options.rust_source_map,
+        standard_library: cargo_toml_options.rust_standard_library,
+        entries: cargo_toml_options.entries?,
+    })
+}
+
+/// A problem in the lily source.
+/// Lines and characters start at 0 and characters are counted in utf-16 code units,
+/// like in the language server protocol
+#[derive(Clone)]
+pub struct LilyError {
+    pub start_line: u32,
+    pub start_character: u32,
+    pub end_line: u32,
+    pub end_character: u32,
+    pub message: String,
+}
+pub struct LilyRustCompilation {
+    /// the generated rust module.
+    /// Like with lily build, it is generated even if there are errors
+    pub rust: String,
+    pub errors: Vec<LilyError>,
+}
+/// Compile the source of a .lily file into the content of a rust module,
+/// exactly what lily build would write
+#[must_use]
+pub fn compile_lily_source_to_rust(source: &str, options: &LilyRustOptions) -> LilyRustCompilation {
+    let lily_syntax_project: compiler::LilySyntaxProject =
+        compiler::parse_lily_syntax_project(source);
+    let mut error_nodes: Vec<compiler::LilyErrorNode> = Vec::new();
+    let compiled_project: compiler::CompiledProject = compiler::lily_project_compile_to_rust(
+        &mut error_nodes,
+        &lily_syntax_project,
+        options.source_map,
+    );
+    // with errors, declarations can be missing so entries are only checked for a valid project
+    let checked_entries: &[String] = if !error_nodes.is_empty() {
+        &[]
+    } else if let Some(unknown_entry) =
+        compiler::compiled_project_first_unknown_entry(&compiled_project, &options.entries)
+    {
+        error_nodes.push(compiler::lily_unknown_entry_error(unknown_entry));
+        &[]
+    } else {
+        &options.entries
+    };
+    let compiled_rust: std::borrow::Cow<syn::File> =
+        compiler::compiled_project_rust_reachable_from_entries(&compiled_project, checked_entries);
+    let errors: Vec<LilyError> = error_nodes
+        .into_iter()
+        .map(|error_node| LilyError {
+            start_line: error_node.range.start.line,
+            start_character: error_node.range.start.character,
+            end_line: error_node.range.end.line,
+            end_character: error_node.range.end.character,
+            message: error_node.message.into_string(),
+        })
+        .collect::<Vec<_>>();
+    LilyRustCompilation {
+        rust: compiler::compiled_rust_to_file_content(
+            &compiled_rust,
+            options.core_inclusion,
+            options.threading,
+            options.serialization,
+            options.source_map,
+            options.standard_library,
+        ),
+        errors: errors,
+    }
+}
+
+/// For a cargo build script: write the rust module lily build would generate
+/// for the given .lily file, by default lily/mod.rs for lily.lily.
+///
+/// The options are taken from the nearest Cargo.toml.
+/// Cargo is told to rerun the build script when the .lily file or Cargo.toml change
+/// and lily errors are reported as cargo warnings.
+/// The module is only written when its content changed
+///
+/// # Errors
+/// When the .lily file can not be read or the module can not be written
+pub fn build_script_generate_rust_module(input_file_path: &std::path::Path) -> Result<(), String> {
+    println!("cargo::rerun-if-changed={}", input_file_path.display());
+    if let Some((cargo_directory_path, _)) =
+        compiler::nearest_cargo_toml_for_input_file_path(input_file_path)
+    {
+        println!(
+            "cargo::rerun-if-changed={}",
+            cargo_directory_path.join("Cargo.toml").display()
+        );
+    }
+    let source: String = std::fs::read_to_string(input_file_path).map_err(|read_error| {
+        format!("was looking for a file with the name {input_file_path:?} but failed: {read_error}")
+    })?;
+    let compilation: LilyRustCompilation = compile_lily_source_to_rust(
+        &source,
+        &lily_rust_options_for_input_file_path(input_file_path)?,
+    );
+    for error in &compilation.errors {
+        let mut message_lines = error.message.lines();
+        println!(
+            "cargo::warning={}:{}:{}: {}",
+            input_file_path.display(),
+            error.start_line + 1,
+            error.start_character + 1,
+            message_lines.next().unwrap_or("")
+        );
+        for message_line in message_lines {
+            println!("cargo::warning=    {message_line}");
+        }
+    }
+    let output_file_path: std::path::PathBuf =
+        compiler::default_lily_output_file_path_for_input_file_path(input_file_path);
+    if std::fs::read_to_string(&output_file_path)
+        .is_ok_and(|existing_output| existing_output == compilation.rust)
+    {
+        return Ok(());
+    }
+    if let Some(output_file_directory_path) = output_file_path.parent() {
+        std::fs::create_dir_all(output_file_directory_path).map_err(|error| {
+            format!(
+                "tried to create the directory containing the output file {output_file_path:?} but failed: {error}"
+            )
+        })?;
+    }
+    std::fs::write(&output_file_path, compilation.rust).map_err(|write_error| {
+        format!(
+            "tried to write the output into the file {output_file_path:?} but failed: {write_error}"
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn generated_rust_names_of_ordinary_identifiers() {
+        let compilation: super::LilyRustCompilation = super::compile_lily_source_to_rust(
+            r"type score-board =
+    { record-score unt
+    , type str
+    }
+
+record-score
+    0
+
+on-click \{ count :unt:count, on-click :unt:clicked } >
+    unt-add count clicked
+",
+            &super::LilyRustOptions {
+                core_inclusion: super::LilyCoreInclusion::Inlined,
+                threading: super::LilyRustThreading::SingleThreaded,
+                serialization: super::LilyRustSerialization::None,
+                source_map: super::LilyRustSourceMap::None,
+                standard_library: super::LilyRustStandardLibrary::Std,
+                entries: vec![],
+            },
+        );
+        assert!(compilation.errors.is_empty());
+        let rust_file: syn::File = syn::parse_file(&compilation.rust).unwrap();
+        let rust_item_signatures: Vec<String> = rust_file
+            .items
+            .iter()
+            .filter_map(|rust_item| match rust_item {
+                syn::Item::Fn(rust_fn) if matches!(rust_fn.vis, syn::Visibility::Public(_)) => {
+                    Some(quote::ToTokens::to_token_stream(&rust_fn.sig).to_string())
+                }
+                syn::Item::Type(rust_type_alias) => Some(rust_type_alias.ident.to_string()),
+                syn::Item::Struct(rust_struct) => Some(rust_struct.ident.to_string()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        for expected_rust_item_signature in [
+            "Score_board",
+            "fn score_board (record_score : Unt , r#type : Str) -> Score_board",
+            "fn record_score () -> Unt",
+            "fn on_click (Record_count_on__click { count : count , on_click : clicked , } : Record_count_on__click < Unt , Unt > ,) -> Unt",
+            "Record_count_on__click",
+            "Record_record__score_type",
+        ] {
+            assert!(
+                rust_item_signatures
+                    .iter()
+                    .any(|rust_item_signature| rust_item_signature == expected_rust_item_signature),
+                "{expected_rust_item_signature} not in {rust_item_signatures:#?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,913 @@
+// core //
+#include <math.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+static void Lily_OutOfMemory(void) {
+    fputs("lily: out of memory\n", stderr);
+    abort();
+}
+static void* Lily_Reallocate(void* allocation, size_t size) {
+    void* reallocated = realloc(allocation, size == 0 ? 1 : size);
+    if (reallocated == NULL) {
+        Lily_OutOfMemory();
+    }
+    return reallocated;
+}
+LilyObject* Lily_Allocate(size_t size, void (*drop)(LilyObject* object)) {
+    LilyObject* object = Lily_Reallocate(NULL, size);
+    object->reference_count = 1;
+    object->drop = drop;
+    return object;
+}
+void Lily_Release(LilyValue value) {
+    LilyObject* object = value.object;
+    if (object == NULL || object->reference_count == 0) {
+        return;
+    }
+    object->reference_count -= 1;
+    if (object->reference_count == 0) {
+        object->drop(object);
+        free(object);
+    }
+}
+LilyValue Lily_Todo(void) {
+    fputs("lily: reached incomplete code. Fix the compile errors first\n", stderr);
+    abort();
+}
+
+static LilyValue Lily_OrderFromComparison(int comparison) {
+    return LilyVariant_WithoutValue(
+        comparison < 0 ? LilyTag_order_Less
+        : comparison > 0 ? LilyTag_order_Greater
+        : LilyTag_order_Equal
+    );
+}
+static LilyValue Lily_OptAbsent(void) {
+    return LilyVariant_WithoutValue(LilyTag_opt_Absent);
+}
+static LilyValue Lily_OptPresent(LilyValue value) {
+    return LilyVariant_New(LilyTag_opt_Present, value);
+}
+
+static void LilyStr_Drop(LilyObject* object) {
+    LilyStr* str = (LilyStr*)object;
+    if (str->capacity != 0) {
+        free(str->bytes);
+    }
+}
+static LilyStr* LilyStr_WithCapacity(size_t capacity) {
+    LilyStr* str = (LilyStr*)Lily_Allocate(sizeof(LilyStr), LilyStr_Drop);
+    str->byte_count = 0;
+    str->capacity = capacity == 0 ? 1 : capacity;
+    str->bytes = Lily_Reallocate(NULL, str->capacity);
+    return str;
+}
+LilyValue LilyStr_FromBytes(const char* bytes, size_t byte_count) {
+    LilyStr* str = LilyStr_WithCapacity(byte_count);
+    memcpy(str->bytes, bytes, byte_count);
+    str->byte_count = byte_count;
+    LilyValue value = { &str->header, { 0 } };
+    return value;
+}
+LilyValue LilyStr_FromStatic(const char* bytes, size_t byte_count) {
+    LilyStr* str = (LilyStr*)Lily_Allocate(sizeof(LilyStr), LilyStr_Drop);
+    str->byte_count = byte_count;
+    str->capacity = 0;
+    str->bytes = (char*)bytes;
+    LilyValue value = { &str->header, { 0 } };
+    return value;
+}
+bool LilyStr_Equals(LilyValue str, const char* bytes, size_t byte_count) {
+    LilyStr* str_object = LilyStr_Of(str);
+    return str_object->byte_count == byte_count
+        && memcmp(str_object->bytes, bytes, byte_count) == 0;
+}
+// like rust's Rc::make_mut: reuses the str if it is only referenced here, otherwise copies it
+static LilyStr* LilyStr_Unique(LilyValue str, size_t additional_capacity) {
+    LilyStr* str_object = LilyStr_Of(str);
+    if (str_object->header.reference_count == 1 && str_object->capacity != 0) {
+        if (str_object->capacity - str_object->byte_count < additional_capacity) {
+            size_t new_capacity = str_object->capacity * 2;
+            if (new_capacity < str_object->byte_count + additional_capacity) {
+                new_capacity = str_object->byte_count + additional_capacity;
+            }
+            str_object->bytes = Lily_Reallocate(str_object->bytes, new_capacity);
+            str_object->capacity = new_capacity;
+        }
+        return str_object;
+    }
+    LilyStr* copy = LilyStr_WithCapacity(str_object->byte_count + additional_capacity);
+    memcpy(copy->bytes, str_object->bytes, str_object->byte_count);
+    copy->byte_count = str_object->byte_count;
+    Lily_Release(str);
+    return copy;
+}
+static LilyValue LilyStr_Value(LilyStr* str) {
+    LilyValue value = { &str->header, { 0 } };
+    return value;
+}
+static LilyValue LilyStr_AttachBytes(LilyValue left, const char* bytes, size_t byte_count) {
+    LilyStr* combined = LilyStr_Unique(left, byte_count);
+    memcpy(combined->bytes + combined->byte_count, bytes, byte_count);
+    combined->byte_count += byte_count;
+    return LilyStr_Value(combined);
+}
+
+static void LilyVec_Drop(LilyObject* object) {
+    LilyVec* vec = (LilyVec*)object;
+    for (size_t index = 0; index < vec->length; index += 1) {
+        Lily_Release(vec->elements[index]);
+    }
+    free(vec->elements);
+}
+static LilyVec* LilyVec_WithCapacity(size_t capacity) {
+    LilyVec* vec = (LilyVec*)Lily_Allocate(sizeof(LilyVec), LilyVec_Drop);
+    vec->length = 0;
+    vec->capacity = capacity;
+    vec->elements = Lily_Reallocate(NULL, capacity * sizeof(LilyValue));
+    return vec;
+}
+static LilyValue LilyVec_Value(LilyVec* vec) {
+    LilyValue value = { &vec->header, { 0 } };
+    return value;
+}
+LilyValue LilyVec_FromArray(size_t length, const LilyValue* elements) {
+    LilyVec* vec = LilyVec_WithCapacity(length);
+    if (length != 0) {
+        memcpy(vec->elements, elements, length * sizeof(LilyValue));
+    }
+    vec->length = length;
+    return LilyVec_Value(vec);
+}
+// like rust's Rc::make_mut: reuses the vec if it is only referenced here, otherwise copies it
+static LilyVec* LilyVec_Unique(LilyValue vec, size_t additional_capacity) {
+    LilyVec* vec_object = LilyVec_Of(vec);
+    if (vec_object->header.reference_count == 1) {
+        if (vec_object->capacity - vec_object->length < additional_capacity) {
+            size_t new_capacity = vec_object->capacity * 2;
+            if (new_capacity < vec_object->length + additional_capacity) {
+                new_capacity = vec_object->length + additional_capacity;
+            }
+            vec_object->elements =
+                Lily_Reallocate(vec_object->elements, new_capacity * sizeof(LilyValue));
+            vec_object->capacity = new_capacity;
+        }
+        return vec_object;
+    }
+    LilyVec* copy = LilyVec_WithCapacity(vec_object->length + additional_capacity);
+    for (size_t index = 0; index < vec_object->length; index += 1) {
+        copy->elements[index] = Lily_Retain(vec_object->elements[index]);
+    }
+    copy->length = vec_object->length;
+    Lily_Release(vec);
+    return copy;
+}
+
+static void LilyVariant_Drop(LilyObject* object) {
+    Lily_Release(((LilyVariant*)object)->value);
+}
+LilyValue LilyVariant_New(int tag, LilyValue value) {
+    LilyVariant* variant = (LilyVariant*)Lily_Allocate(sizeof(LilyVariant), LilyVariant_Drop);
+    variant->tag = tag;
+    variant->value = value;
+    LilyValue variant_value = { &variant->header, { 0 } };
+    return variant_value;
+}
+
+static void LilyClosure_Drop(LilyObject* object) {
+    LilyClosure* closure = (LilyClosure*)object;
+    for (size_t index = 0; index < closure->capture_count; index += 1) {
+        Lily_Release(closure->captures[index]);
+    }
+}
+LilyValue LilyClosure_New(
+    LilyValue (*call)(LilyClosure* closure, LilyValue* arguments),
+    size_t capture_count,
+    const LilyValue* captures
+) {
+    LilyClosure* closure = (LilyClosure*)Lily_Allocate(
+        sizeof(LilyClosure) + capture_count * sizeof(LilyValue),
+        LilyClosure_Drop
+    );
+    closure->call = call;
+    closure->capture_count = capture_count;
+    if (capture_count != 0) {
+        memcpy(closure->captures, captures, capture_count * sizeof(LilyValue));
+    }
+    LilyValue value = { &closure->header, { 0 } };
+    return value;
+}
+LilyValue LilyClosure_Call(LilyValue function, LilyValue* arguments) {
+    LilyValue result = ((LilyClosure*)function.object)->call((LilyClosure*)function.object, arguments);
+    Lily_Release(function);
+    return result;
+}
+
+// like rust's Display for integers
+static LilyValue LilyStr_AttachUnt(LilyValue left, size_t unt) {
+    char digits[24];
+    int digit_count = snprintf(digits, sizeof(digits), "%zu", unt);
+    return LilyStr_AttachBytes(left, digits, (size_t)digit_count);
+}
+static LilyValue LilyStr_AttachInt(LilyValue left, ptrdiff_t int_) {
+    char digits[24];
+    int digit_count = snprintf(digits, sizeof(digits), "%td", int_);
+    return LilyStr_AttachBytes(left, digits, (size_t)digit_count);
+}
+// like rust's f64 Display: the shortest representation that parses back to the same dec,
+// never in exponent notation
+static LilyValue LilyStr_AttachDec(LilyValue left, double dec) {
+    if (isnan(dec)) {
+        return LilyStr_AttachBytes(left, "NaN", 3);
+    }
+    if (isinf(dec)) {
+        return dec < 0 ? LilyStr_AttachBytes(left, "-inf", 4) : LilyStr_AttachBytes(left, "inf", 3);
+    }
+    if (dec == 0) {
+        return signbit(dec) ? LilyStr_AttachBytes(left, "-0", 2) : LilyStr_AttachBytes(left, "0", 1);
+    }
+    char exponent_notation[32];
+    for (int precision = 0; precision < 17; precision += 1) {
+        snprintf(exponent_notation, sizeof(exponent_notation), "%.*e", precision, dec);
+        if (strtod(exponent_notation, NULL) == dec) {
+            break;
+        }
+    }
+    // d.ddde+x or -d.ddde-x
+    char digits[24];
+    size_t digit_count = 0;
+    const char* exponent_notation_rest = exponent_notation;
+    bool is_negative = *exponent_notation_rest == '-';
+    if (is_negative) {
+        exponent_notation_rest += 1;
+    }
+    for (; *exponent_notation_rest != 'e'; exponent_notation_rest += 1) {
+        if (*exponent_notation_rest != '.') {
+            digits[digit_count] = *exponent_notation_rest;
+            digit_count += 1;
+        }
+    }
+    long point_index = strtol(exponent_notation_rest + 1, NULL, 10) + 1;
+    LilyValue combined = left;
+    if (is_negative) {
+        combined = LilyStr_AttachBytes(combined, "-", 1);
+    }
+    if (point_index <= 0) {
+        combined = LilyStr_AttachBytes(combined, "0.", 2);
+        for (long zero_index = 0; zero_index < -point_index; zero_index += 1) {
+            combined = LilyStr_AttachBytes(combined, "0", 1);
+        }
+        return LilyStr_AttachBytes(combined, digits, digit_count);
+    }
+    if ((size_t)point_index >= digit_count) {
+        combined = LilyStr_AttachBytes(combined, digits, digit_count);
+        for (size_t zero_index = digit_count; zero_index < (size_t)point_index; zero_index += 1) {
+            combined = LilyStr_AttachBytes(combined, "0", 1);
+        }
+        return combined;
+    }
+    combined = LilyStr_AttachBytes(combined, digits, (size_t)point_index);
+    combined = LilyStr_AttachBytes(combined, ".", 1);
+    return LilyStr_AttachBytes(combined, digits + point_index, digit_count - (size_t)point_index);
+}
+static size_t Lily_CharEncodeUtf8(uint32_t code_point, char* bytes) {
+    if (code_point < 0x80) {
+        bytes[0] = (char)code_point;
+        return 1;
+    }
+    if (code_point < 0x800) {
+        bytes[0] = (char)(0xC0 | (code_point >> 6));
+        bytes[1] = (char)(0x80 | (code_point & 0x3F));
+        return 2;
+    }
+    if (code_point < 0x10000) {
+        bytes[0] = (char)(0xE0 | (code_point >> 12));
+        bytes[1] = (char)(0x80 | ((code_point >> 6) & 0x3F));
+        bytes[2] = (char)(0x80 | (code_point & 0x3F));
+        return 3;
+    }
+    bytes[0] = (char)(0xF0 | (code_point >> 18));
+    bytes[1] = (char)(0x80 | ((code_point >> 12) & 0x3F));
+    bytes[2] = (char)(0x80 | ((code_point >> 6) & 0x3F));
+    bytes[3] = (char)(0x80 | (code_point & 0x3F));
+    return 4;
+}
+// the str is valid utf-8, so the index has to be at the start of a char
+static uint32_t Lily_CharDecodeUtf8(const char* bytes, size_t* byte_index) {
+    const unsigned char* unsigned_bytes = (const unsigned char*)bytes + *byte_index;
+    if (unsigned_bytes[0] < 0x80) {
+        *byte_index += 1;
+        return unsigned_bytes[0];
+    }
+    if (unsigned_bytes[0] < 0xE0) {
+        *byte_index += 2;
+        return ((uint32_t)(unsigned_bytes[0] & 0x1F) << 6) | (unsigned_bytes[1] & 0x3F);
+    }
+    if (unsigned_bytes[0] < 0xF0) {
+        *byte_index += 3;
+        return ((uint32_t)(unsigned_bytes[0] & 0x0F) << 12)
+            | ((uint32_t)(unsigned_bytes[1] & 0x3F) << 6)
+            | (unsigned_bytes[2] & 0x3F);
+    }
+    *byte_index += 4;
+    return ((uint32_t)(unsigned_bytes[0] & 0x07) << 18)
+        | ((uint32_t)(unsigned_bytes[1] & 0x3F) << 12)
+        | ((uint32_t)(unsigned_bytes[2] & 0x3F) << 6)
+        | (unsigned_bytes[3] & 0x3F);
+}
+static bool Lily_IsCharBoundary(const LilyStr* str, size_t byte_index) {
+    return byte_index >= str->byte_count || (str->bytes[byte_index] & 0xC0) != 0x80;
+}
+// like rust's str::floor_char_boundary
+static size_t Lily_FloorCharBoundary(const LilyStr* str, size_t byte_index) {
+    if (byte_index >= str->byte_count) {
+        return str->byte_count;
+    }
+    while (!Lily_IsCharBoundary(str, byte_index)) {
+        byte_index -= 1;
+    }
+    return byte_index;
+}
+// like rust's str::ceil_char_boundary
+static size_t Lily_CeilCharBoundary(const LilyStr* str, size_t byte_index) {
+    if (byte_index >= str->byte_count) {
+        return str->byte_count;
+    }
+    while (!Lily_IsCharBoundary(str, byte_index)) {
+        byte_index += 1;
+    }
+    return byte_index;
+}
+// like rust's str::parse for unsigned and signed integers
+static bool Lily_ParseDigits(const LilyStr* str, size_t start_index, size_t maximum, size_t* parsed) {
+    if (start_index >= str->byte_count) {
+        return false;
+    }
+    size_t result = 0;
+    for (size_t index = start_index; index < str->byte_count; index += 1) {
+        char byte = str->bytes[index];
+        if (byte < '0' || byte > '9') {
+            return false;
+        }
+        size_t digit = (size_t)(byte - '0');
+        if (result > (maximum - digit) / 10) {
+            return false;
+        }
+        result = result * 10 + digit;
+    }
+    *parsed = result;
+    return true;
+}
+
+LilyValue lily_unt_add(LilyValue a, LilyValue b) {
+    return LilyUnt_From(a.scalar.unt + b.scalar.unt);
+}
+LilyValue lily_unt_mul(LilyValue a, LilyValue b) {
+    return LilyUnt_From(a.scalar.unt * b.scalar.unt);
+}
+LilyValue lily_unt_div(LilyValue to_divide, LilyValue to_divide_by) {
+    return LilyUnt_From(
+        to_divide_by.scalar.unt == 0 ? 0 : to_divide.scalar.unt / to_divide_by.scalar.unt
+    );
+}
+LilyValue lily_unt_order(LilyValue left, LilyValue right) {
+    return Lily_OrderFromComparison((left.scalar.unt > right.scalar.unt) - (left.scalar.unt < right.scalar.unt));
+}
+LilyValue lily_unt_to_int(LilyValue unt) {
+    return LilyInt_From((ptrdiff_t)unt.scalar.unt);
+}
+LilyValue lily_unt_to_dec(LilyValue unt) {
+    return LilyDec_From((double)unt.scalar.unt);
+}
+LilyValue lily_unt_to_str(LilyValue unt) {
+    return LilyStr_AttachUnt(LilyStr_FromStatic("", 0), unt.scalar.unt);
+}
+LilyValue lily_str_to_unt(LilyValue str) {
+    const LilyStr* str_object = LilyStr_Of(str);
+    size_t start_index = str_object->byte_count != 0 && str_object->bytes[0] == '+' ? 1 : 0;
+    size_t unt;
+    bool is_valid = Lily_ParseDigits(str_object, start_index, SIZE_MAX, &unt);
+    Lily_Release(str);
+    return is_valid ? Lily_OptPresent(LilyUnt_From(unt)) : Lily_OptAbsent();
+}
+
+// wrapping like rust with overflow checks disabled, without signed overflow being undefined
+LilyValue lily_int_negate(LilyValue int_) {
+    return LilyInt_From((ptrdiff_t)(0 - (size_t)int_.scalar.int_));
+}
+LilyValue lily_int_absolute(LilyValue int_) {
+    return LilyUnt_From(int_.scalar.int_ < 0 ? 0 - (size_t)int_.scalar.int_ : (size_t)int_.scalar.int_);
+}
+LilyValue lily_int_add(LilyValue a, LilyValue b) {
+    return LilyInt_From((ptrdiff_t)((size_t)a.scalar.int_ + (size_t)b.scalar.int_));
+}
+LilyValue lily_int_mul(LilyValue a, LilyValue b) {
+    return LilyInt_From((ptrdiff_t)((size_t)a.scalar.int_ * (size_t)b.scalar.int_));
+}
+LilyValue lily_int_div(LilyValue to_divide, LilyValue to_divide_by) {
+    // like rust's checked_div, also 0 for the overflowing minimum / -1
+    if (to_divide_by.scalar.int_ == 0
+        || (to_divide.scalar.int_ == PTRDIFF_MIN && to_divide_by.scalar.int_ == -1)) {
+        return LilyInt_From(0);
+    }
+    return LilyInt_From(to_divide.scalar.int_ / to_divide_by.scalar.int_);
+}
+LilyValue lily_int_order(LilyValue left, LilyValue right) {
+    return Lily_OrderFromComparison((left.scalar.int_ > right.scalar.int_) - (left.scalar.int_ < right.scalar.int_));
+}
+LilyValue lily_int_to_unt(LilyValue int_) {
+    return int_.scalar.int_ >= 0 ? Lily_OptPresent(LilyUnt_From((size_t)int_.scalar.int_)) : Lily_OptAbsent();
+}
+LilyValue lily_int_to_dec(LilyValue int_) {
+    return LilyDec_From((double)int_.scalar.int_);
+}
+LilyValue lily_int_to_str(LilyValue int_) {
+    return LilyStr_AttachInt(LilyStr_FromStatic("", 0), int_.scalar.int_);
+}
+LilyValue lily_str_to_int(LilyValue str) {
+    const LilyStr* str_object = LilyStr_Of(str);
+    bool is_negative = str_object->byte_count != 0 && str_object->bytes[0] == '-';
+    size_t start_index = str_object->byte_count != 0 && (is_negative || str_object->bytes[0] == '+') ? 1 : 0;
+    size_t magnitude;
+    bool is_valid = Lily_ParseDigits(
+        str_object,
+        start_index,
+        is_negative ? (size_t)PTRDIFF_MAX + 1 : (size_t)PTRDIFF_MAX,
+        &magnitude
+    );
+    Lily_Release(str);
+    return is_valid
+        ? Lily_OptPresent(LilyInt_From(is_negative ? (ptrdiff_t)(0 - magnitude) : (ptrdiff_t)magnitude))
+        : Lily_OptAbsent();
+}
+
+LilyValue lily_dec_pi(void) {
+    return LilyDec_From(3.14159265358979323846264338327950288);
+}
+LilyValue lily_dec_negate(LilyValue dec) {
+    return LilyDec_From(-dec.scalar.dec);
+}
+LilyValue lily_dec_absolute(LilyValue dec) {
+    return LilyDec_From(fabs(dec.scalar.dec));
+}
+LilyValue lily_dec_ln(LilyValue dec) {
+    return dec.scalar.dec <= 0 ? Lily_OptAbsent() : Lily_OptPresent(LilyDec_From(log(dec.scalar.dec)));
+}
+LilyValue lily_dec_sin(LilyValue dec) {
+    return LilyDec_From(sin(dec.scalar.dec));
+}
+LilyValue lily_dec_cos(LilyValue dec) {
+    return LilyDec_From(cos(dec.scalar.dec));
+}
+LilyValue lily_dec_tan(LilyValue dec) {
+    return LilyDec_From(tan(dec.scalar.dec));
+}
+LilyValue lily_dec_atan(LilyValue dec) {
+    return LilyDec_From(atan(dec.scalar.dec));
+}
+LilyValue lily_dec_atan2(LilyValue y, LilyValue x) {
+    return LilyDec_From(atan2(y.scalar.dec, x.scalar.dec));
+}
+LilyValue lily_dec_add(LilyValue a, LilyValue b) {
+    return LilyDec_From(a.scalar.dec + b.scalar.dec);
+}
+LilyValue lily_dec_mul(LilyValue a, LilyValue b) {
+    return LilyDec_From(a.scalar.dec * b.scalar.dec);
+}
+LilyValue lily_dec_div(LilyValue to_divide, LilyValue to_divide_by) {
+    return LilyDec_From(to_divide_by.scalar.dec == 0 ? 0 : to_divide.scalar.dec / to_divide_by.scalar.dec);
+}
+LilyValue lily_dec_to_power_of(LilyValue dec, LilyValue exponent) {
+    return LilyDec_From(pow(dec.scalar.dec, exponent.scalar.dec));
+}
+// like rust's saturating `as` casts
+static LilyValue LilyInt_FromDec(double dec) {
+    if (isnan(dec)) {
+        return LilyInt_From(0);
+    }
+    if (dec <= (double)PTRDIFF_MIN) {
+        return LilyInt_From(PTRDIFF_MIN);
+    }
+    if (dec >= (double)PTRDIFF_MAX) {
+        return LilyInt_From(PTRDIFF_MAX);
+    }
+    return LilyInt_From((ptrdiff_t)dec);
+}
+LilyValue lily_dec_truncate(LilyValue dec) {
+    return LilyInt_FromDec(trunc(dec.scalar.dec));
+}
+LilyValue lily_dec_floor(LilyValue dec) {
+    return LilyInt_FromDec(floor(dec.scalar.dec));
+}
+LilyValue lily_dec_ceiling(LilyValue dec) {
+    return LilyInt_FromDec(ceil(dec.scalar.dec));
+}
+LilyValue lily_dec_round(LilyValue dec) {
+    // half away from zero, like rust
+    return LilyInt_FromDec(round(dec.scalar.dec));
+}
+LilyValue lily_dec_order(LilyValue left, LilyValue right) {
+    // NaN is considered equal to everything, like in lily_core.rs
+    return Lily_OrderFromComparison((left.scalar.dec > right.scalar.dec) - (left.scalar.dec < right.scalar.dec));
+}
+LilyValue lily_dec_to_str(LilyValue dec) {
+    return LilyStr_AttachDec(LilyStr_FromStatic("", 0), dec.scalar.dec);
+}
+static bool Lily_EqualsIgnoringAsciiCase(const char* bytes, size_t byte_count, const char* lowercase) {
+    if (strlen(lowercase) != byte_count) {
+        return false;
+    }
+    for (size_t index = 0; index < byte_count; index += 1) {
+        char byte = bytes[index];
+        if ((byte >= 'A' && byte <= 'Z' ? byte - 'A' + 'a' : byte) != lowercase[index]) {
+            return false;
+        }
+    }
+    return true;
+}
+// like rust's str::parse for f64
+static bool Lily_IsDecSyntax(const LilyStr* str) {
+    size_t index = 0;
+    if (index < str->byte_count && (str->bytes[index] == '+' || str->bytes[index] == '-')) {
+        index += 1;
+    }
+    size_t rest_byte_count = str->byte_count - index;
+    const char* rest = str->bytes + index;
+    if (Lily_EqualsIgnoringAsciiCase(rest, rest_byte_count, "inf")
+        || Lily_EqualsIgnoringAsciiCase(rest, rest_byte_count, "infinity")
+        || Lily_EqualsIgnoringAsciiCase(rest, rest_byte_count, "nan")) {
+        return true;
+    }
+    size_t digit_count = 0;
+    while (index < str->byte_count && str->bytes[index] >= '0' && str->bytes[index] <= '9') {
+        index += 1;
+        digit_count += 1;
+    }
+    if (index < str->byte_count && str->bytes[index] == '.') {
+        index += 1;
+        while (index < str->byte_count && str->bytes[index] >= '0' && str->bytes[index] <= '9') {
+            index += 1;
+            digit_count += 1;
+        }
+    }
+    if (digit_count == 0) {
+        return false;
+    }
+    if (index < str->byte_count && (str->bytes[index] == 'e' || str->bytes[index] == 'E')) {
+        index += 1;
+        if (index < str->byte_count && (str->bytes[index] == '+' || str->bytes[index] == '-')) {
+            index += 1;
+        }
+        size_t exponent_digit_count = 0;
+        while (index < str->byte_count && str->bytes[index] >= '0' && str->bytes[index] <= '9') {
+            index += 1;
+            exponent_digit_count += 1;
+        }
+        if (exponent_digit_count == 0) {
+            return false;
+        }
+    }
+    return index == str->byte_count;
+}
+LilyValue lily_str_to_dec(LilyValue str) {
+    const LilyStr* str_object = LilyStr_Of(str);
+    if (!Lily_IsDecSyntax(str_object)) {
+        Lily_Release(str);
+        return Lily_OptAbsent();
+    }
+    char* terminated = Lily_Reallocate(NULL, str_object->byte_count + 1);
+    memcpy(terminated, str_object->bytes, str_object->byte_count);
+    terminated[str_object->byte_count] = '\0';
+    double dec = strtod(terminated, NULL);
+    free(terminated);
+    Lily_Release(str);
+    return Lily_OptPresent(LilyDec_From(dec));
+}
+
+LilyValue lily_char_byte_count(LilyValue char_) {
+    char bytes[4];
+    return LilyUnt_From(Lily_CharEncodeUtf8(char_.scalar.char_, bytes));
+}
+LilyValue lily_char_order(LilyValue left, LilyValue right) {
+    return Lily_OrderFromComparison((left.scalar.char_ > right.scalar.char_) - (left.scalar.char_ < right.scalar.char_));
+}
+LilyValue lily_code_point_to_char(LilyValue code_point) {
+    size_t unt = code_point.scalar.unt;
+    return unt <= 0x10FFFF && !(unt >= 0xD800 && unt <= 0xDFFF)
+        ? Lily_OptPresent(LilyChar_From((uint32_t)unt))
+        : Lily_OptAbsent();
+}
+LilyValue lily_char_to_code_point(LilyValue char_) {
+    return LilyUnt_From(char_.scalar.char_);
+}
+LilyValue lily_char_to_str(LilyValue char_) {
+    char bytes[4];
+    return LilyStr_FromBytes(bytes, Lily_CharEncodeUtf8(char_.scalar.char_, bytes));
+}
+
+// indexes are counted in utf-8 bytes like in rust
+LilyValue lily_str_byte_count(LilyValue str) {
+    size_t byte_count = LilyStr_Of(str)->byte_count;
+    Lily_Release(str);
+    return LilyUnt_From(byte_count);
+}
+LilyValue lily_str_char_at_byte_index(LilyValue str, LilyValue byte_index) {
+    const LilyStr* str_object = LilyStr_Of(str);
+    size_t char_byte_index = Lily_FloorCharBoundary(str_object, byte_index.scalar.unt);
+    LilyValue result = char_byte_index >= str_object->byte_count
+        ? Lily_OptAbsent()
+        : Lily_OptPresent(LilyChar_From(Lily_CharDecodeUtf8(str_object->bytes, &char_byte_index)));
+    Lily_Release(str);
+    return result;
+}
+LilyValue lily_str_slice_from_byte_index_with_byte_length(
+    LilyValue str,
+    LilyValue start_index,
+    LilyValue slice_byte_length
+) {
+    // all chars that overlap the byte range, like rust's floor_char_boundary..ceil_char_boundary
+    const LilyStr* str_object = LilyStr_Of(str);
+    size_t slice_start_index = Lily_FloorCharBoundary(str_object, start_index.scalar.unt);
+    size_t slice_end_index =
+        Lily_CeilCharBoundary(str_object, start_index.scalar.unt + slice_byte_length.scalar.unt);
+    LilyValue slice = slice_start_index >= slice_end_index
+        ? LilyStr_FromStatic("", 0)
+        : LilyStr_FromBytes(str_object->bytes + slice_start_index, slice_end_index - slice_start_index);
+    Lily_Release(str);
+    return slice;
+}
+LilyValue lily_str_to_chars(LilyValue str) {
+    const LilyStr* str_object = LilyStr_Of(str);
+    LilyVec* chars = LilyVec_WithCapacity(str_object->byte_count);
+    size_t byte_index = 0;
+    while (byte_index < str_object->byte_count) {
+        chars->elements[chars->length] = LilyChar_From(Lily_CharDecodeUtf8(str_object->bytes, &byte_index));
+        chars->length += 1;
+    }
+    Lily_Release(str);
+    return LilyVec_Value(chars);
+}
+LilyValue lily_chars_to_str(LilyValue chars) {
+    const LilyVec* chars_object = LilyVec_Of(chars);
+    LilyStr* str = LilyStr_WithCapacity(chars_object->length * 4);
+    for (size_t index = 0; index < chars_object->length; index += 1) {
+        str->byte_count +=
+            Lily_CharEncodeUtf8(chars_object->elements[index].scalar.char_, str->bytes + str->byte_count);
+    }
+    Lily_Release(chars);
+    return LilyStr_Value(str);
+}
+LilyValue lily_str_order(LilyValue left, LilyValue right) {
+    // comparing utf-8 bytes is the same as comparing code points like rust
+    const LilyStr* left_object = LilyStr_Of(left);
+    const LilyStr* right_object = LilyStr_Of(right);
+    size_t common_byte_count =
+        left_object->byte_count < right_object->byte_count ? left_object->byte_count : right_object->byte_count;
+    int comparison = common_byte_count == 0 ? 0 : memcmp(left_object->bytes, right_object->bytes, common_byte_count);
+    if (comparison == 0) {
+        comparison = (left_object->byte_count > right_object->byte_count)
+            - (left_object->byte_count < right_object->byte_count);
+    }
+    Lily_Release(left);
+    Lily_Release(right);
+    return Lily_OrderFromComparison(comparison);
+}
+LilyValue lily_str_walk_chars_from(LilyValue str, LilyValue initial_state, LilyValue on_element) {
+    const LilyStr* str_object = LilyStr_Of(str);
+    LilyValue state = initial_state;
+    size_t byte_index = 0;
+    while (byte_index < str_object->byte_count) {
+        LilyValue arguments[2] = { state, LilyChar_From(Lily_CharDecodeUtf8(str_object->bytes, &byte_index)) };
+        LilyValue step = LilyClosure_Call(Lily_Retain(on_element), arguments);
+        if (LilyVariant_Tag(step) == LilyTag_continue_or_exit__Exit) {
+            Lily_Release(str);
+            Lily_Release(on_element);
+            return step;
+        }
+        state = Lily_Retain(LilyVariant_Value(step));
+        Lily_Release(step);
+    }
+    Lily_Release(str);
+    Lily_Release(on_element);
+    return LilyVariant_New(LilyTag_continue_or_exit__Continue, state);
+}
+LilyValue lily_str_attach_char(LilyValue left, LilyValue right) {
+    char bytes[4];
+    return LilyStr_AttachBytes(left, bytes, Lily_CharEncodeUtf8(right.scalar.char_, bytes));
+}
+LilyValue lily_str_attach_unt(LilyValue left, LilyValue right) {
+    return LilyStr_AttachUnt(left, right.scalar.unt);
+}
+LilyValue lily_str_attach_int(LilyValue left, LilyValue right) {
+    return LilyStr_AttachInt(left, right.scalar.int_);
+}
+LilyValue lily_str_attach_dec(LilyValue left, LilyValue right) {
+    return LilyStr_AttachDec(left, right.scalar.dec);
+}
+LilyValue lily_str_attach(LilyValue left, LilyValue right) {
+    const LilyStr* right_object = LilyStr_Of(right);
+    LilyValue combined = LilyStr_AttachBytes(left, right_object->bytes, right_object->byte_count);
+    Lily_Release(right);
+    return combined;
+}
+LilyValue lily_strs_flatten(LilyValue vec_of_str) {
+    const LilyVec* vec_object = LilyVec_Of(vec_of_str);
+    size_t byte_count = 0;
+    for (size_t index = 0; index < vec_object->length; index += 1) {
+        byte_count += LilyStr_Of(vec_object->elements[index])->byte_count;
+    }
+    LilyStr* flattened = LilyStr_WithCapacity(byte_count);
+    for (size_t index = 0; index < vec_object->length; index += 1) {
+        const LilyStr* element = LilyStr_Of(vec_object->elements[index]);
+        memcpy(flattened->bytes + flattened->byte_count, element->bytes, element->byte_count);
+        flattened->byte_count += element->byte_count;
+    }
+    Lily_Release(vec_of_str);
+    return LilyStr_Value(flattened);
+}
+
+LilyValue lily_vec_repeat(LilyValue length, LilyValue element) {
+    LilyVec* vec = LilyVec_WithCapacity(length.scalar.unt);
+    for (size_t index = 0; index < length.scalar.unt; index += 1) {
+        vec->elements[index] = Lily_Retain(element);
+    }
+    vec->length = length.scalar.unt;
+    Lily_Release(element);
+    return LilyVec_Value(vec);
+}
+LilyValue lily_vec_by_index_for_length(LilyValue length, LilyValue index_to_element) {
+    LilyVec* vec = LilyVec_WithCapacity(length.scalar.unt);
+    for (size_t index = 0; index < length.scalar.unt; index += 1) {
+        LilyValue arguments[1] = { LilyUnt_From(index) };
+        vec->elements[index] = LilyClosure_Call(Lily_Retain(index_to_element), arguments);
+        vec->length = index + 1;
+    }
+    Lily_Release(index_to_element);
+    return LilyVec_Value(vec);
+}
+LilyValue lily_vec_length(LilyValue vec) {
+    size_t length = LilyVec_Of(vec)->length;
+    Lily_Release(vec);
+    return LilyUnt_From(length);
+}
+LilyValue lily_vec_element(LilyValue vec, LilyValue index) {
+    const LilyVec* vec_object = LilyVec_Of(vec);
+    LilyValue result = index.scalar.unt < vec_object->length
+        ? Lily_OptPresent(Lily_Retain(vec_object->elements[index.scalar.unt]))
+        : Lily_OptAbsent();
+    Lily_Release(vec);
+    return result;
+}
+LilyValue lily_vec_replace_element(LilyValue vec, LilyValue index, LilyValue new_element) {
+    if (index.scalar.unt >= LilyVec_Of(vec)->length) {
+        Lily_Release(new_element);
+        return vec;
+    }
+    LilyVec* owned_vec = LilyVec_Unique(vec, 0);
+    Lily_Release(owned_vec->elements[index.scalar.unt]);
+    owned_vec->elements[index.scalar.unt] = new_element;
+    return LilyVec_Value(owned_vec);
+}
+LilyValue lily_vec_swap(LilyValue vec, LilyValue a_index, LilyValue b_index) {
+    size_t length = LilyVec_Of(vec)->length;
+    if (a_index.scalar.unt >= length || b_index.scalar.unt >= length || a_index.scalar.unt == b_index.scalar.unt) {
+        return vec;
+    }
+    LilyVec* owned_vec = LilyVec_Unique(vec, 0);
+    LilyValue a_element = owned_vec->elements[a_index.scalar.unt];
+    owned_vec->elements[a_index.scalar.unt] = owned_vec->elements[b_index.scalar.unt];
+    owned_vec->elements[b_index.scalar.unt] = a_element;
+    return LilyVec_Value(owned_vec);
+}
+LilyValue lily_vec_truncate(LilyValue vec, LilyValue taken_length) {
+    if (taken_length.scalar.unt >= LilyVec_Of(vec)->length) {
+        return vec;
+    }
+    LilyVec* owned_vec = LilyVec_Unique(vec, 0);
+    for (size_t index = taken_length.scalar.unt; index < owned_vec->length; index += 1) {
+        Lily_Release(owned_vec->elements[index]);
+    }
+    owned_vec->length = taken_length.scalar.unt;
+    return LilyVec_Value(owned_vec);
+}
+LilyValue lily_vec_slice_from_index_with_length(
+    LilyValue vec,
+    LilyValue start_index,
+    LilyValue slice_length
+) {
+    const LilyVec* vec_object = LilyVec_Of(vec);
+    size_t slice_start_index = start_index.scalar.unt;
+    if (slice_start_index >= vec_object->length) {
+        Lily_Release(vec);
+        return LilyVec_FromArray(0, NULL);
+    }
+    size_t slice_end_index = vec_object->length - slice_start_index < slice_length.scalar.unt
+        ? vec_object->length
+        : slice_start_index + slice_length.scalar.unt;
+    LilyVec* slice = LilyVec_WithCapacity(slice_end_index - slice_start_index);
+    for (size_t index = slice_start_index; index < slice_end_index; index += 1) {
+        slice->elements[slice->length] = Lily_Retain(vec_object->elements[index]);
+        slice->length += 1;
+    }
+    Lily_Release(vec);
+    return LilyVec_Value(slice);
+}
+LilyValue lily_vec_increase_capacity_by(LilyValue vec, LilyValue capacity_increase) {
+    return LilyVec_Value(LilyVec_Unique(vec, capacity_increase.scalar.unt));
+}
+// merge sort, because qsort can not be given the element order closure
+static void Lily_SortBy(LilyValue* elements, LilyValue* buffer, size_t length, LilyValue element_order) {
+    if (length <= 1) {
+        return;
+    }
+    size_t middle_index = length / 2;
+    Lily_SortBy(elements, buffer, middle_index, element_order);
+    Lily_SortBy(elements + middle_index, buffer, length - middle_index, element_order);
+    size_t left_index = 0;
+    size_t right_index = middle_index;
+    size_t merged_length = 0;
+    while (left_index < middle_index && right_index < length) {
+        LilyValue arguments[2] = { Lily_Retain(elements[right_index]), Lily_Retain(elements[left_index]) };
+        LilyValue order = LilyClosure_Call(Lily_Retain(element_order), arguments);
+        if (LilyVariant_Tag(order) == LilyTag_order_Less) {
+            buffer[merged_length] = elements[right_index];
+            right_index += 1;
+        } else {
+            buffer[merged_length] = elements[left_index];
+            left_index += 1;
+        }
+        merged_length += 1;
+    }
+    while (left_index < middle_index) {
+        buffer[merged_length] = elements[left_index];
+        left_index += 1;
+        merged_length += 1;
+    }
+    while (right_index < length) {
+        buffer[merged_length] = elements[right_index];
+        right_index += 1;
+        merged_length += 1;
+    }
+    memcpy(elements, buffer, length * sizeof(LilyValue));
+}
+LilyValue lily_vec_sort(LilyValue vec, LilyValue element_order) {
+    LilyVec* owned_vec = LilyVec_Unique(vec, 0);
+    LilyValue* buffer = Lily_Reallocate(NULL, owned_vec->length * sizeof(LilyValue));
+    Lily_SortBy(owned_vec->elements, buffer, owned_vec->length, element_order);
+    free(buffer);
+    Lily_Release(element_order);
+    return LilyVec_Value(owned_vec);
+}
+LilyValue lily_vec_attach_element(LilyValue left, LilyValue right_element) {
+    LilyVec* combined = LilyVec_Unique(left, 1);
+    combined->elements[combined->length] = right_element;
+    combined->length += 1;
+    return LilyVec_Value(combined);
+}
+LilyValue lily_vec_attach(LilyValue left, LilyValue right) {
+    const LilyVec* right_object = LilyVec_Of(right);
+    LilyVec* combined = LilyVec_Unique(left, right_object->length);
+    for (size_t index = 0; index < right_object->length; index += 1) {
+        combined->elements[combined->length] = Lily_Retain(right_object->elements[index]);
+        combined->length += 1;
+    }
+    Lily_Release(right);
+    return LilyVec_Value(combined);
+}
+LilyValue lily_vec_flatten(LilyValue vec_vec) {
+    const LilyVec* vec_vec_object = LilyVec_Of(vec_vec);
+    size_t length = 0;
+    for (size_t index = 0; index < vec_vec_object->length; index += 1) {
+        length += LilyVec_Of(vec_vec_object->elements[index])->length;
+    }
+    LilyVec* flattened = LilyVec_WithCapacity(length);
+    for (size_t index = 0; index < vec_vec_object->length; index += 1) {
+        const LilyVec* inner = LilyVec_Of(vec_vec_object->elements[index]);
+        for (size_t inner_index = 0; inner_index < inner->length; inner_index += 1) {
+            flattened->elements[flattened->length] = Lily_Retain(inner->elements[inner_index]);
+            flattened->length += 1;
+        }
+    }
+    Lily_Release(vec_vec);
+    return LilyVec_Value(flattened);
+}
+LilyValue lily_vec_walk_from(LilyValue vec, LilyValue initial_state, LilyValue on_element) {
+    const LilyVec* vec_object = LilyVec_Of(vec);
+    LilyValue state = initial_state;
+    for (size_t index = 0; index < vec_object->length; index += 1) {
+        LilyValue arguments[2] = { state, Lily_Retain(vec_object->elements[index]) };
+        LilyValue step = LilyClosure_Call(Lily_Retain(on_element), arguments);
+        if (LilyVariant_Tag(step) == LilyTag_continue_or_exit__Exit) {
+            Lily_Release(vec);
+            Lily_Release(on_element);
+            return step;
+        }
+        state = Lily_Retain(LilyVariant_Value(step));
+        Lily_Release(step);
+    }
+    Lily_Release(vec);
+    Lily_Release(on_element);
+    return LilyVariant_New(LilyTag_continue_or_exit__Continue, state);
+}
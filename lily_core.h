@@ -0,0 +1,251 @@
+// core //
+// Every lily value is a LilyValue.
+// unt, int, dec, char, {} and variants without a value are stored directly in it,
+// str, vec, records, variants with a value and functions are reference-counted objects.
+// Functions take ownership of their arguments and return a value you own:
+// call Lily_Retain before passing a value you still want to use
+// and Lily_Release once you are done with a value.
+// Objects that are only referenced once are mutated in place instead of being copied,
+// so values must not be shared between threads.
+// Helpers start with Lily and compiled lily declarations with lily_ so they can not collide
+#ifndef LILY_CORE_H
+#define LILY_CORE_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+typedef struct LilyObject {
+    // 0 for objects that are never freed
+    size_t reference_count;
+    // releases what the object references, called right before it is freed
+    void (*drop)(struct LilyObject* object);
+} LilyObject;
+
+typedef struct LilyValue {
+    // NULL for values that are stored directly
+    LilyObject* object;
+    union {
+        size_t unt;
+        ptrdiff_t int_;
+        double dec;
+        // unicode code point
+        uint32_t char_;
+        // of a variant without a value
+        int tag;
+    } scalar;
+} LilyValue;
+
+typedef struct LilyStr {
+    LilyObject header;
+    size_t byte_count;
+    // 0 when the bytes are not owned, like for string literals
+    size_t capacity;
+    // utf-8, not 0-terminated
+    char* bytes;
+} LilyStr;
+
+typedef struct LilyVec {
+    LilyObject header;
+    size_t length;
+    size_t capacity;
+    LilyValue* elements;
+} LilyVec;
+
+// A choice type variant with a value.
+// The generated LilyChoice_ structs have the same layout with a union of named values
+typedef struct LilyVariant {
+    LilyObject header;
+    int tag;
+    LilyValue value;
+} LilyVariant;
+
+typedef struct LilyClosure {
+    LilyObject header;
+    // takes ownership of the arguments, but not of the closure
+    struct LilyValue (*call)(struct LilyClosure* closure, LilyValue* arguments);
+    size_t capture_count;
+    LilyValue captures[];
+} LilyClosure;
+
+enum {
+    LilyTag_order_Less,
+    LilyTag_order_Equal,
+    LilyTag_order_Greater,
+};
+enum {
+    LilyTag_opt_Absent,
+    LilyTag_opt_Present,
+};
+enum {
+    LilyTag_continue_or_exit__Continue,
+    LilyTag_continue_or_exit__Exit,
+};
+
+static inline LilyValue Lily_Retain(LilyValue value) {
+    if (value.object != NULL && value.object->reference_count != 0) {
+        value.object->reference_count += 1;
+    }
+    return value;
+}
+void Lily_Release(LilyValue value);
+// Called when reaching incomplete lily code, like rust's todo!()
+LilyValue Lily_Todo(void);
+// Allocates an object with a reference count of 1
+LilyObject* Lily_Allocate(size_t size, void (*drop)(LilyObject* object));
+static inline LilyValue Lily_ObjectValue(LilyObject* object) {
+    LilyValue value = { object, { 0 } };
+    return value;
+}
+
+static inline LilyValue Lily_Blank(void) {
+    LilyValue value = { NULL, { 0 } };
+    return value;
+}
+static inline LilyValue LilyUnt_From(size_t unt) {
+    LilyValue value = { NULL, { 0 } };
+    value.scalar.unt = unt;
+    return value;
+}
+static inline LilyValue LilyInt_From(ptrdiff_t int_) {
+    LilyValue value = { NULL, { 0 } };
+    value.scalar.int_ = int_;
+    return value;
+}
+static inline LilyValue LilyDec_From(double dec) {
+    LilyValue value = { NULL, { 0 } };
+    value.scalar.dec = dec;
+    return value;
+}
+static inline LilyValue LilyChar_From(uint32_t char_) {
+    LilyValue value = { NULL, { 0 } };
+    value.scalar.char_ = char_;
+    return value;
+}
+
+// Copies the utf-8 bytes
+LilyValue LilyStr_FromBytes(const char* bytes, size_t byte_count);
+// References the utf-8 bytes, which therefore have to live as long as the program
+LilyValue LilyStr_FromStatic(const char* bytes, size_t byte_count);
+// Borrows the str
+bool LilyStr_Equals(LilyValue str, const char* bytes, size_t byte_count);
+static inline LilyStr* LilyStr_Of(LilyValue str) {
+    return (LilyStr*)str.object;
+}
+
+// Copies the elements array, taking ownership of each element
+LilyValue LilyVec_FromArray(size_t length, const LilyValue* elements);
+static inline LilyVec* LilyVec_Of(LilyValue vec) {
+    return (LilyVec*)vec.object;
+}
+
+LilyValue LilyVariant_New(int tag, LilyValue value);
+static inline LilyValue LilyVariant_WithoutValue(int tag) {
+    LilyValue value = { NULL, { 0 } };
+    value.scalar.tag = tag;
+    return value;
+}
+// Borrows the variant
+static inline int LilyVariant_Tag(LilyValue variant) {
+    return variant.object == NULL ? variant.scalar.tag : ((LilyVariant*)variant.object)->tag;
+}
+// Borrows the variant and its value
+static inline LilyValue LilyVariant_Value(LilyValue variant) {
+    return ((LilyVariant*)variant.object)->value;
+}
+
+// Copies the captures array, taking ownership of each capture
+LilyValue LilyClosure_New(
+    LilyValue (*call)(LilyClosure* closure, LilyValue* arguments),
+    size_t capture_count,
+    const LilyValue* captures
+);
+// Takes ownership of the function and the arguments
+LilyValue LilyClosure_Call(LilyValue function, LilyValue* arguments);
+
+LilyValue lily_unt_add(LilyValue a, LilyValue b);
+LilyValue lily_unt_mul(LilyValue a, LilyValue b);
+LilyValue lily_unt_div(LilyValue to_divide, LilyValue to_divide_by);
+LilyValue lily_unt_order(LilyValue left, LilyValue right);
+LilyValue lily_unt_to_int(LilyValue unt);
+LilyValue lily_unt_to_dec(LilyValue unt);
+LilyValue lily_unt_to_str(LilyValue unt);
+LilyValue lily_str_to_unt(LilyValue str);
+
+LilyValue lily_int_negate(LilyValue int_);
+LilyValue lily_int_absolute(LilyValue int_);
+LilyValue lily_int_add(LilyValue a, LilyValue b);
+LilyValue lily_int_mul(LilyValue a, LilyValue b);
+LilyValue lily_int_div(LilyValue to_divide, LilyValue to_divide_by);
+LilyValue lily_int_order(LilyValue left, LilyValue right);
+LilyValue lily_int_to_unt(LilyValue int_);
+LilyValue lily_int_to_dec(LilyValue int_);
+LilyValue lily_int_to_str(LilyValue int_);
+LilyValue lily_str_to_int(LilyValue str);
+
+// Please sanitize before passing it to lily (drop infinities and NaN)
+LilyValue lily_dec_pi(void);
+LilyValue lily_dec_negate(LilyValue dec);
+LilyValue lily_dec_absolute(LilyValue dec);
+LilyValue lily_dec_ln(LilyValue dec);
+LilyValue lily_dec_sin(LilyValue dec);
+LilyValue lily_dec_cos(LilyValue dec);
+LilyValue lily_dec_tan(LilyValue dec);
+LilyValue lily_dec_atan(LilyValue dec);
+LilyValue lily_dec_atan2(LilyValue y, LilyValue x);
+LilyValue lily_dec_add(LilyValue a, LilyValue b);
+LilyValue lily_dec_mul(LilyValue a, LilyValue b);
+LilyValue lily_dec_div(LilyValue to_divide, LilyValue to_divide_by);
+LilyValue lily_dec_to_power_of(LilyValue dec, LilyValue exponent);
+LilyValue lily_dec_truncate(LilyValue dec);
+LilyValue lily_dec_floor(LilyValue dec);
+LilyValue lily_dec_ceiling(LilyValue dec);
+LilyValue lily_dec_round(LilyValue dec);
+LilyValue lily_dec_order(LilyValue left, LilyValue right);
+LilyValue lily_dec_to_str(LilyValue dec);
+LilyValue lily_str_to_dec(LilyValue str);
+
+LilyValue lily_char_byte_count(LilyValue char_);
+LilyValue lily_char_order(LilyValue left, LilyValue right);
+LilyValue lily_code_point_to_char(LilyValue code_point);
+LilyValue lily_char_to_code_point(LilyValue char_);
+LilyValue lily_char_to_str(LilyValue char_);
+
+LilyValue lily_str_byte_count(LilyValue str);
+LilyValue lily_str_char_at_byte_index(LilyValue str, LilyValue byte_index);
+LilyValue lily_str_slice_from_byte_index_with_byte_length(
+    LilyValue str,
+    LilyValue start_index,
+    LilyValue slice_byte_length
+);
+LilyValue lily_str_to_chars(LilyValue str);
+LilyValue lily_chars_to_str(LilyValue chars);
+LilyValue lily_str_order(LilyValue left, LilyValue right);
+LilyValue lily_str_walk_chars_from(LilyValue str, LilyValue initial_state, LilyValue on_element);
+LilyValue lily_str_attach_char(LilyValue left, LilyValue right);
+LilyValue lily_str_attach_unt(LilyValue left, LilyValue right);
+LilyValue lily_str_attach_int(LilyValue left, LilyValue right);
+LilyValue lily_str_attach_dec(LilyValue left, LilyValue right);
+LilyValue lily_str_attach(LilyValue left, LilyValue right);
+LilyValue lily_strs_flatten(LilyValue vec_of_str);
+
+LilyValue lily_vec_repeat(LilyValue length, LilyValue element);
+LilyValue lily_vec_by_index_for_length(LilyValue length, LilyValue index_to_element);
+LilyValue lily_vec_length(LilyValue vec);
+LilyValue lily_vec_element(LilyValue vec, LilyValue index);
+LilyValue lily_vec_replace_element(LilyValue vec, LilyValue index, LilyValue new_element);
+LilyValue lily_vec_swap(LilyValue vec, LilyValue a_index, LilyValue b_index);
+LilyValue lily_vec_truncate(LilyValue vec, LilyValue taken_length);
+LilyValue lily_vec_slice_from_index_with_length(
+    LilyValue vec,
+    LilyValue start_index,
+    LilyValue slice_length
+);
+LilyValue lily_vec_increase_capacity_by(LilyValue vec, LilyValue capacity_increase);
+LilyValue lily_vec_sort(LilyValue vec, LilyValue element_order);
+LilyValue lily_vec_attach_element(LilyValue left, LilyValue right_element);
+LilyValue lily_vec_attach(LilyValue left, LilyValue right);
+LilyValue lily_vec_flatten(LilyValue vec_vec);
+LilyValue lily_vec_walk_from(LilyValue vec, LilyValue initial_state, LilyValue on_element);
+
+#endif
@@ -0,0 +1,359 @@
+// core //
+// unt, int and dec are numbers, char and str are strings, vec is an array,
+// records are plain objects with _ instead of - in field names
+// and choice type variants are objects like { tag: "Present", value: 3 } or { tag: "Absent" }.
+// Values are never mutated.
+// Helpers start with $ so they can not collide with compiled lily names
+
+function $todo(message) {
+    throw new Error(message);
+}
+
+const $order_less = Object.freeze({ tag: "Less" });
+const $order_equal = Object.freeze({ tag: "Equal" });
+const $order_greater = Object.freeze({ tag: "Greater" });
+function $order_from_comparison(left, right) {
+    return left < right ? $order_less : left > right ? $order_greater : $order_equal;
+}
+function $order_to_number(order) {
+    return order.tag === "Less" ? -1 : order.tag === "Greater" ? 1 : 0;
+}
+
+const $opt_absent = Object.freeze({ tag: "Absent" });
+function $opt_present(value) {
+    return { tag: "Present", value: value };
+}
+
+function $int_to_string(int) {
+    return Number.isSafeInteger(int) ? String(int) : BigInt(int).toString();
+}
+// like rust's f64 Display which never uses exponent notation
+function $dec_to_string(dec) {
+    if (Number.isNaN(dec)) {
+        return "NaN";
+    }
+    if (dec === Infinity) {
+        return "inf";
+    }
+    if (dec === -Infinity) {
+        return "-inf";
+    }
+    if (Object.is(dec, -0)) {
+        return "-0";
+    }
+    const string = String(dec);
+    const exponent_index = string.indexOf("e");
+    if (exponent_index === -1) {
+        return string;
+    }
+    const sign = dec < 0 ? "-" : "";
+    const [integer_digits, fraction_digits = ""] = string
+        .slice(sign.length, exponent_index)
+        .split(".");
+    const digits = integer_digits + fraction_digits;
+    const point_index = integer_digits.length + Number(string.slice(exponent_index + 1));
+    if (point_index <= 0) {
+        return sign + "0." + "0".repeat(-point_index) + digits;
+    }
+    if (point_index >= digits.length) {
+        return sign + digits + "0".repeat(point_index - digits.length);
+    }
+    return sign + digits.slice(0, point_index) + "." + digits.slice(point_index);
+}
+// like rust's saturating `as` casts, with + 0 turning -0 into 0
+function $dec_to_int(dec) {
+    return Number.isNaN(dec) ? 0 : dec + 0;
+}
+
+export function unt_add(a, b) {
+    return a + b;
+}
+export function unt_mul(a, b) {
+    return a * b;
+}
+export function unt_div(to_divide, to_divide_by) {
+    return to_divide_by === 0 ? 0 : Math.trunc(to_divide / to_divide_by);
+}
+export function unt_order(left, right) {
+    return $order_from_comparison(left, right);
+}
+export function unt_to_int(unt) {
+    return unt;
+}
+export function unt_to_dec(unt) {
+    return unt;
+}
+export function unt_to_str(unt) {
+    return $int_to_string(unt);
+}
+export function str_to_unt(str) {
+    return /^\+?[0-9]+$/.test(str) ? $opt_present(Number(str)) : $opt_absent;
+}
+
+export function int_negate(int) {
+    return 0 - int;
+}
+export function int_absolute(int) {
+    return Math.abs(int);
+}
+export function int_add(a, b) {
+    return a + b;
+}
+export function int_mul(a, b) {
+    return a * b + 0;
+}
+export function int_div(to_divide, to_divide_by) {
+    return to_divide_by === 0 ? 0 : Math.trunc(to_divide / to_divide_by) + 0;
+}
+export function int_order(left, right) {
+    return $order_from_comparison(left, right);
+}
+export function int_to_unt(int) {
+    return int >= 0 ? $opt_present(int) : $opt_absent;
+}
+export function int_to_dec(int) {
+    return int;
+}
+export function int_to_str(int) {
+    return $int_to_string(int);
+}
+export function str_to_int(str) {
+    return /^[+-]?[0-9]+$/.test(str) ? $opt_present(Number(str) + 0) : $opt_absent;
+}
+
+// Please sanitize before passing it to lily (drop infinities and NaN)
+export const dec_pi = Math.PI;
+export function dec_negate(dec) {
+    return -dec;
+}
+export function dec_absolute(dec) {
+    return Math.abs(dec);
+}
+export function dec_ln(dec) {
+    return dec <= 0 ? $opt_absent : $opt_present(Math.log(dec));
+}
+export function dec_sin(dec) {
+    return Math.sin(dec);
+}
+export function dec_cos(dec) {
+    return Math.cos(dec);
+}
+export function dec_tan(dec) {
+    return Math.tan(dec);
+}
+export function dec_atan(dec) {
+    return Math.atan(dec);
+}
+export function dec_atan2(y, x) {
+    return Math.atan2(y, x);
+}
+export function dec_add(a, b) {
+    return a + b;
+}
+export function dec_mul(a, b) {
+    return a * b;
+}
+export function dec_div(to_divide, to_divide_by) {
+    return to_divide_by === 0 ? 0 : to_divide / to_divide_by;
+}
+export function dec_to_power_of(dec, exponent) {
+    return Math.pow(dec, exponent);
+}
+export function dec_truncate(dec) {
+    return $dec_to_int(Math.trunc(dec));
+}
+export function dec_floor(dec) {
+    return $dec_to_int(Math.floor(dec));
+}
+export function dec_ceiling(dec) {
+    return $dec_to_int(Math.ceil(dec));
+}
+export function dec_round(dec) {
+    // half away from zero like rust, not towards +infinity like Math.round
+    return $dec_to_int(Math.sign(dec) * Math.round(Math.abs(dec)));
+}
+export function dec_order(left, right) {
+    // NaN is considered equal to everything, like in lily_core.rs
+    return $order_from_comparison(left, right);
+}
+export function dec_to_str(dec) {
+    return $dec_to_string(dec);
+}
+export function str_to_dec(str) {
+    if (!/^[+-]?(inf|infinity|nan|([0-9]+\.?[0-9]*|\.[0-9]+)([eE][+-]?[0-9]+)?)$/i.test(str)) {
+        return $opt_absent;
+    }
+    const unsigned = str.replace(/^[+-]/, "").toLowerCase();
+    const sign = str.startsWith("-") ? -1 : 1;
+    return $opt_present(
+        unsigned === "nan" ? NaN : unsigned.startsWith("inf") ? sign * Infinity : Number(str)
+    );
+}
+
+export function char_byte_count(char) {
+    const code_point = char.codePointAt(0);
+    return code_point < 0x80 ? 1 : code_point < 0x800 ? 2 : code_point < 0x10000 ? 3 : 4;
+}
+export function char_order(left, right) {
+    return $order_from_comparison(left.codePointAt(0), right.codePointAt(0));
+}
+export function code_point_to_char(code_point) {
+    return Number.isInteger(code_point) &&
+        code_point >= 0 &&
+        code_point <= 0x10ffff &&
+        !(code_point >= 0xd800 && code_point <= 0xdfff)
+        ? $opt_present(String.fromCodePoint(code_point))
+        : $opt_absent;
+}
+export function char_to_code_point(char) {
+    return char.codePointAt(0);
+}
+export function char_to_str(char) {
+    return char;
+}
+
+// indexes are counted in utf-8 bytes like in rust, not in utf-16 code units
+export function str_byte_count(str) {
+    let byte_count = 0;
+    for (const char of str) {
+        byte_count += char_byte_count(char);
+    }
+    return byte_count;
+}
+export function str_char_at_byte_index(str, byte_index) {
+    let char_byte_index = 0;
+    for (const char of str) {
+        char_byte_index += char_byte_count(char);
+        if (char_byte_index > byte_index) {
+            return $opt_present(char);
+        }
+    }
+    return $opt_absent;
+}
+export function str_slice_from_byte_index_with_byte_length(str, start_index, slice_byte_length) {
+    // all chars that overlap the byte range, like rust's floor_char_boundary..ceil_char_boundary
+    const end_index = start_index + slice_byte_length;
+    let slice = "";
+    let char_byte_index = 0;
+    for (const char of str) {
+        if (char_byte_index >= end_index) {
+            break;
+        }
+        const char_end_byte_index = char_byte_index + char_byte_count(char);
+        if (char_end_byte_index > start_index) {
+            slice += char;
+        }
+        char_byte_index = char_end_byte_index;
+    }
+    return slice;
+}
+export function str_to_chars(str) {
+    return Array.from(str);
+}
+export function chars_to_str(chars) {
+    return chars.join("");
+}
+export function str_order(left, right) {
+    // by code point like rust, not by utf-16 code unit like <
+    let index = 0;
+    while (index < left.length && index < right.length) {
+        const left_code_point = left.codePointAt(index);
+        const right_code_point = right.codePointAt(index);
+        if (left_code_point !== right_code_point) {
+            return $order_from_comparison(left_code_point, right_code_point);
+        }
+        index += left_code_point > 0xffff ? 2 : 1;
+    }
+    return $order_from_comparison(left.length - index, right.length - index);
+}
+export function str_walk_chars_from(str, initial_state, on_element) {
+    let state = initial_state;
+    for (const char of str) {
+        const step = on_element(state, char);
+        if (step.tag === "Exit") {
+            return step;
+        }
+        state = step.value;
+    }
+    return { tag: "Continue", value: state };
+}
+export function str_attach_char(left, right) {
+    return left + right;
+}
+export function str_attach_unt(left, right) {
+    return left + $int_to_string(right);
+}
+export function str_attach_int(left, right) {
+    return left + $int_to_string(right);
+}
+export function str_attach_dec(left, right) {
+    return left + $dec_to_string(right);
+}
+export function str_attach(left, right) {
+    return left + right;
+}
+export function strs_flatten(vec_of_str) {
+    return vec_of_str.join("");
+}
+
+export function vec_repeat(length, element) {
+    return new Array(length).fill(element);
+}
+export function vec_by_index_for_length(length, index_to_element) {
+    return Array.from({ length: length }, (_, index) => index_to_element(index));
+}
+export function vec_length(vec) {
+    return vec.length;
+}
+export function vec_element(vec, index) {
+    return index < vec.length ? $opt_present(vec[index]) : $opt_absent;
+}
+export function vec_replace_element(vec, index, new_element) {
+    if (index >= vec.length) {
+        return vec;
+    }
+    const owned_vec = vec.slice();
+    owned_vec[index] = new_element;
+    return owned_vec;
+}
+export function vec_swap(vec, a_index, b_index) {
+    if (a_index >= vec.length || b_index >= vec.length || a_index === b_index) {
+        return vec;
+    }
+    const owned_vec = vec.slice();
+    owned_vec[a_index] = vec[b_index];
+    owned_vec[b_index] = vec[a_index];
+    return owned_vec;
+}
+export function vec_truncate(vec, taken_length) {
+    return taken_length >= vec.length ? vec : vec.slice(0, taken_length);
+}
+export function vec_slice_from_index_with_length(vec, start_index, slice_length) {
+    return vec.slice(start_index, start_index + slice_length);
+}
+export function vec_increase_capacity_by(vec, _capacity_increase) {
+    return vec;
+}
+export function vec_sort(vec, element_order) {
+    return vec.slice().sort((a, b) => $order_to_number(element_order(a, b)));
+}
+export function vec_attach_element(left, right_element) {
+    return [...left, right_element];
+}
+export function vec_attach(left, right) {
+    return left.concat(right);
+}
+export function vec_flatten(vec_vec) {
+    return vec_vec.flat();
+}
+export function vec_walk_from(vec, initial_state, on_element) {
+    let state = initial_state;
+    for (const element of vec) {
+        const step = on_element(state, element);
+        if (step.tag === "Exit") {
+            return step;
+        }
+        state = step.value;
+    }
+    return { tag: "Continue", value: state };
+}
@@ -3,6 +3,7 @@
     dead_code,
     non_shorthand_field_patterns,
     non_camel_case_types,
+    non_upper_case_globals,
     clippy::needless_pass_by_value,
     clippy::wrong_self_convention,
     clippy::redundant_field_names,
@@ -320,6 +321,11 @@ impl PartialOrd<str> for Str {
         std::option::Option::Some(self.as_str().cmp(other))
     }
 }
+impl Hash for Str {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
 
 fn str_byte_count(str: Str) -> Unt {
     str.as_str().len()
@@ -429,6 +435,21 @@ impl<A: PartialEq> PartialEq for Vec<A> {
         self.as_slice().eq(other.as_slice())
     }
 }
+impl<A: Ord> Ord for Vec<A> {
+    fn cmp(&self, other: &Vec<A>) -> std::cmp::Ordering {
+        self.as_slice().cmp(other.as_slice())
+    }
+}
+impl<A: PartialOrd> PartialOrd for Vec<A> {
+    fn partial_cmp(&self, other: &Vec<A>) -> std::option::Option<std::cmp::Ordering> {
+        self.as_slice().partial_cmp(other.as_slice())
+    }
+}
+impl<A: Hash> Hash for Vec<A> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state);
+    }
+}
 impl<A> std::convert::AsRef<[A]> for Vec<A> {
     fn as_ref(&self) -> &[A] {
         self.as_slice()
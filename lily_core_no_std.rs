@@ -0,0 +1,247 @@
+
+// core no_std dec math //
+
+/// The dec operations `core` does not provide, which would otherwise come from the platform's libm.
+/// Since f64 has no such inherent methods without std, `Dec::sin(dec)` etc. resolve to these.
+/// Results can differ from std's in the last bits,
+/// and sin, cos and tan of decs beyond about 10^6 are imprecise
+trait DecFallbackMath {
+    fn trunc(self) -> Self;
+    fn floor(self) -> Self;
+    fn ceil(self) -> Self;
+    fn round(self) -> Self;
+    fn exp(self) -> Self;
+    fn ln(self) -> Self;
+    fn powf(self, exponent: Self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn tan(self) -> Self;
+    fn atan(self) -> Self;
+    fn atan2(self, x: Self) -> Self;
+}
+/// 2^52, all decs with at least this magnitude are whole numbers
+const dec_fallback_integer_threshold: Dec = 4503599627370496.0;
+/// ln 2 split into a part whose multiples up to 2^11 are exact and the small rest
+const dec_fallback_ln_2_high: Dec = 6.931471803691238e-1;
+const dec_fallback_ln_2_low: Dec = 1.9082149292705877e-10;
+/// pi/2 split into a part whose multiples up to 2^20 are exact and the small rest
+const dec_fallback_frac_pi_2_high: Dec = 1.5707963267341256;
+const dec_fallback_frac_pi_2_low: Dec = 6.077100506506192e-11;
+/// 2^exponent for -1022 <= exponent <= 1023
+fn dec_fallback_power_of_2(exponent: i64) -> Dec {
+    Dec::from_bits(((exponent + 1023) as u64) << 52)
+}
+/// sine and cosine of a dec between -pi/4 and pi/4
+fn dec_fallback_sin_cos_near_0(dec: Dec) -> (Dec, Dec) {
+    let dec_squared: Dec = dec * dec;
+    let mut sin: Dec = dec;
+    let mut sin_term: Dec = dec;
+    let mut cos: Dec = 1.0;
+    let mut cos_term: Dec = 1.0;
+    let mut n: Dec = 1.0;
+    while n < 26.0 {
+        cos_term = -cos_term * dec_squared / (n * (n + 1.0));
+        cos += cos_term;
+        sin_term = -sin_term * dec_squared / ((n + 1.0) * (n + 2.0));
+        sin += sin_term;
+        n += 2.0;
+    }
+    (sin, cos)
+}
+fn dec_fallback_sin_cos(dec: Dec) -> (Dec, Dec) {
+    let quarter_turns: Dec = Dec::round(dec / std::f64::consts::FRAC_PI_2);
+    let (sin, cos): (Dec, Dec) = dec_fallback_sin_cos_near_0(
+        dec - quarter_turns * dec_fallback_frac_pi_2_high
+            - quarter_turns * dec_fallback_frac_pi_2_low,
+    );
+    match (quarter_turns as i64) & 3 {
+        0 => (sin, cos),
+        1 => (cos, -sin),
+        2 => (-sin, -cos),
+        _ => (-cos, sin),
+    }
+}
+impl DecFallbackMath for Dec {
+    fn trunc(self) -> Dec {
+        // also keeps infinities and NaN
+        if Dec::is_nan(self) || Dec::abs(self) >= dec_fallback_integer_threshold {
+            self
+        } else {
+            (self as i64) as Dec
+        }
+    }
+    fn floor(self) -> Dec {
+        let truncated: Dec = Dec::trunc(self);
+        if truncated > self {
+            truncated - 1.0
+        } else {
+            truncated
+        }
+    }
+    fn ceil(self) -> Dec {
+        let truncated: Dec = Dec::trunc(self);
+        if truncated < self {
+            truncated + 1.0
+        } else {
+            truncated
+        }
+    }
+    /// half-way cases away from 0
+    fn round(self) -> Dec {
+        let truncated: Dec = Dec::trunc(self);
+        let fraction: Dec = self - truncated;
+        if fraction >= 0.5 {
+            truncated + 1.0
+        } else if fraction <= -0.5 {
+            truncated - 1.0
+        } else {
+            truncated
+        }
+    }
+    fn exp(self) -> Dec {
+        if Dec::is_nan(self) {
+            return self;
+        }
+        if self > 709.8 {
+            return Dec::INFINITY;
+        }
+        if self < -745.2 {
+            return 0.0;
+        }
+        let power_of_2: Dec = Dec::round(self / std::f64::consts::LN_2);
+        let remainder: Dec =
+            self - power_of_2 * dec_fallback_ln_2_high - power_of_2 * dec_fallback_ln_2_low;
+        let mut result: Dec = 1.0;
+        let mut term: Dec = 1.0;
+        let mut n: Dec = 1.0;
+        while n < 24.0 {
+            term = term * remainder / n;
+            result += term;
+            n += 1.0;
+        }
+        // split so both factors stay representable
+        let power_of_2_half: i64 = (power_of_2 as i64) / 2;
+        result
+            * dec_fallback_power_of_2(power_of_2_half)
+            * dec_fallback_power_of_2((power_of_2 as i64) - power_of_2_half)
+    }
+    fn ln(self) -> Dec {
+        if self < 0.0 {
+            return Dec::NAN;
+        }
+        if self == 0.0 {
+            return -Dec::INFINITY;
+        }
+        if Dec::is_nan(self) || self == Dec::INFINITY {
+            return self;
+        }
+        if self < Dec::MIN_POSITIVE {
+            // subnormal, scale by 2^54 first
+            return Dec::ln(self * 18014398509481984.0) - 54.0 * std::f64::consts::LN_2;
+        }
+        let bits: u64 = Dec::to_bits(self);
+        let mut exponent: i64 = ((bits >> 52) & 0x7ff) as i64 - 1023;
+        // between 1 and 2
+        let mut mantissa: Dec =
+            Dec::from_bits((bits & 0x000f_ffff_ffff_ffff) | 0x3ff0_0000_0000_0000);
+        if mantissa > std::f64::consts::SQRT_2 {
+            mantissa /= 2.0;
+            exponent += 1;
+        }
+        // ln mantissa = 2 atanh s
+        let s: Dec = (mantissa - 1.0) / (mantissa + 1.0);
+        let s_squared: Dec = s * s;
+        let mut atanh: Dec = 0.0;
+        let mut s_power: Dec = s;
+        let mut n: Dec = 1.0;
+        while n < 34.0 {
+            atanh += s_power / n;
+            s_power *= s_squared;
+            n += 2.0;
+        }
+        2.0 * atanh + (exponent as Dec) * std::f64::consts::LN_2
+    }
+    fn powf(self, exponent: Dec) -> Dec {
+        if exponent == 0.0 {
+            return 1.0;
+        }
+        if Dec::trunc(exponent) == exponent && Dec::abs(exponent) < dec_fallback_integer_threshold {
+            // exact for small whole results, like 2^10
+            let mut remaining_exponent: i64 = Dec::abs(exponent) as i64;
+            let mut base: Dec = self;
+            let mut result: Dec = 1.0;
+            while remaining_exponent > 0 {
+                if remaining_exponent & 1 == 1 {
+                    result *= base;
+                }
+                base *= base;
+                remaining_exponent >>= 1;
+            }
+            return if exponent < 0.0 { 1.0 / result } else { result };
+        }
+        if self < 0.0 {
+            Dec::NAN
+        } else if self == 0.0 {
+            if exponent > 0.0 { 0.0 } else { Dec::INFINITY }
+        } else {
+            Dec::exp(exponent * Dec::ln(self))
+        }
+    }
+    fn sin(self) -> Dec {
+        dec_fallback_sin_cos(self).0
+    }
+    fn cos(self) -> Dec {
+        dec_fallback_sin_cos(self).1
+    }
+    fn tan(self) -> Dec {
+        let (sin, cos): (Dec, Dec) = dec_fallback_sin_cos(self);
+        sin / cos
+    }
+    fn atan(self) -> Dec {
+        if self < 0.0 {
+            return -Dec::atan(-self);
+        }
+        if self > 1.0 {
+            return std::f64::consts::FRAC_PI_2 - Dec::atan(1.0 / self);
+        }
+        // atan x = pi/6 + atan ((x sqrt 3 - 1) / (sqrt 3 + x))
+        // brings x from up to 1 down to at most tan (pi/12)
+        let sqrt_3: Dec = 1.7320508075688772;
+        let (offset, reduced): (Dec, Dec) = if self > 0.2679491924311227 {
+            (
+                std::f64::consts::FRAC_PI_6,
+                (self * sqrt_3 - 1.0) / (sqrt_3 + self),
+            )
+        } else {
+            (0.0, self)
+        };
+        let reduced_squared: Dec = reduced * reduced;
+        let mut atan: Dec = 0.0;
+        let mut reduced_power: Dec = reduced;
+        let mut n: Dec = 1.0;
+        while n < 34.0 {
+            atan += reduced_power / n;
+            reduced_power *= -reduced_squared;
+            n += 2.0;
+        }
+        offset + atan
+    }
+    fn atan2(self, x: Dec) -> Dec {
+        let y: Dec = self;
+        if x > 0.0 {
+            Dec::atan(y / x)
+        } else if x < 0.0 {
+            if y >= 0.0 {
+                Dec::atan(y / x) + std::f64::consts::PI
+            } else {
+                Dec::atan(y / x) - std::f64::consts::PI
+            }
+        } else if y > 0.0 {
+            std::f64::consts::FRAC_PI_2
+        } else if y < 0.0 {
+            -std::f64::consts::FRAC_PI_2
+        } else {
+            0.0
+        }
+    }
+}
@@ -0,0 +1,39 @@
+
+// core serde //
+
+impl serde::Serialize for Str {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+impl<'de> serde::Deserialize<'de> for Str {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Str, D::Error> {
+        std::result::Result::map(
+            <std::string::String as serde::Deserialize>::deserialize(deserializer),
+            Str::from_string,
+        )
+    }
+}
+impl<A: serde::Serialize> serde::Serialize for Vec<A> {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        serde::Serialize::serialize(self.as_slice(), serializer)
+    }
+}
+impl<'de, A: serde::Deserialize<'de>> serde::Deserialize<'de> for Vec<A> {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Vec<A>, D::Error> {
+        std::result::Result::map(
+            <std::vec::Vec<A> as serde::Deserialize>::deserialize(deserializer),
+            Vec::from_vec,
+        )
+    }
+}